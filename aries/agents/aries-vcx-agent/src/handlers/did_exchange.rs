@@ -74,6 +74,8 @@ impl<T: BaseWallet> DidcommHandlerDidExchange<T> {
             invitation_id,
             &their_did,
             &our_peer_did,
+            None,
+            None,
         )
         .await?;
 
@@ -125,7 +127,7 @@ impl<T: BaseWallet> DidcommHandlerDidExchange<T> {
         //       multiple IDs (both thid, pthid (or arbitrary other))
         self.did_exchange.insert(&thid, (requester.clone(), None))?;
         VcxHttpClient
-            .send_message(encryption_envelope.0, service.service_endpoint())
+            .send_message(encryption_envelope.into_bytes(), service.service_endpoint())
             .await?;
         Ok((thid, pthid))
     }
@@ -218,7 +220,7 @@ impl<T: BaseWallet> DidcommHandlerDidExchange<T> {
         )
         .await?;
         VcxHttpClient
-            .send_message(encryption_envelope.0, service.service_endpoint())
+            .send_message(encryption_envelope.into_bytes(), service.service_endpoint())
             .await?;
         info!("ServiceDidExchange::send_response <<< successfully sent response");
         Ok(thid)
@@ -246,7 +248,7 @@ impl<T: BaseWallet> DidcommHandlerDidExchange<T> {
         .await?;
         self.did_exchange.insert(&thid, (requester.clone(), None))?;
         VcxHttpClient
-            .send_message(encryption_envelope.0, service.service_endpoint())
+            .send_message(encryption_envelope.into_bytes(), service.service_endpoint())
             .await?;
         Ok(thid)
     }
@@ -2,15 +2,36 @@ mod verifier_internal;
 
 use aries_vcx_anoncreds::anoncreds::base_anoncreds::BaseAnonCreds;
 use aries_vcx_ledger::ledger::base_ledger::AnoncredsLedgerRead;
+use messages::msg_fields::protocols::present_proof::v1::{
+    present::PresentationV1, request::RequestPresentationV1,
+};
 
 use crate::{
     common::proofs::verifier::verifier_internal::{
         build_cred_defs_json_verifier, build_rev_reg_defs_json, build_rev_reg_json,
-        build_schemas_json_verifier, get_credential_info, validate_proof_revealed_attributes,
+        build_schemas_json_verifier, get_credential_info, get_proof_referents,
+        validate_proof_revealed_attributes,
     },
     errors::error::prelude::*,
+    handlers::util::get_attach_as_string,
 };
 
+/// Outcome of verifying a presentation outside of a proof exchange.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresentationVerificationResult {
+    pub valid: bool,
+    /// Referents of the requested attributes satisfied by the presentation. Empty if the
+    /// presentation is not `valid`.
+    pub attributes: Vec<String>,
+    /// Referents of the requested predicates satisfied by the presentation. Empty if the
+    /// presentation is not `valid`.
+    pub predicates: Vec<String>,
+    /// Whether every credential the presentation draws on carries a non-revocation proof, which
+    /// `valid` then covers. A presentation only contains such proofs for credentials the request
+    /// set a `non_revoked` interval for, so this is `false` whenever revocation was not asked for.
+    pub revocation_checked: bool,
+}
+
 pub async fn validate_indy_proof(
     ledger: &impl AnoncredsLedgerRead,
     anoncreds: &impl BaseAnonCreds,
@@ -48,3 +69,203 @@ pub async fn validate_indy_proof(
         .await
         .map_err(|err| err.into())
 }
+
+/// Verifies a presentation against a presentation request, without requiring the stateful
+/// verifier. Useful when the presentation was obtained out of band.
+///
+/// A presentation which fails validation or is rejected by the verifier (e.g. a requested
+/// attribute is missing or a revocation interval is not met) yields `valid: false`. An error is
+/// returned only if the presentation or request cannot be processed at all, e.g. malformed
+/// attachments or ledger lookups failing.
+pub async fn verify_presentation(
+    ledger: &impl AnoncredsLedgerRead,
+    anoncreds: &impl BaseAnonCreds,
+    presentation_request: &RequestPresentationV1,
+    presentation: &PresentationV1,
+) -> VcxResult<PresentationVerificationResult> {
+    let proof_json = get_attach_as_string!(&presentation.content.presentations_attach);
+    let proof_req_json =
+        get_attach_as_string!(&presentation_request.content.request_presentations_attach);
+
+    let valid = match validate_indy_proof(ledger, anoncreds, &proof_json, &proof_req_json).await {
+        Ok(valid) => valid,
+        Err(err)
+            if matches!(
+                err.kind(),
+                AriesVcxErrorKind::InvalidProof | AriesVcxErrorKind::ProofRejected
+            ) =>
+        {
+            info!("verify_presentation >> presentation failed validation: {err}");
+            false
+        }
+        Err(err) => return Err(err),
+    };
+    let (attributes, predicates) = if valid {
+        get_proof_referents(&proof_json)?
+    } else {
+        (vec![], vec![])
+    };
+    let credential_data = get_credential_info(&proof_json)?;
+    let revocation_checked = !credential_data.is_empty()
+        && credential_data
+            .iter()
+            .all(|cred_info| cred_info.rev_reg_id.is_some() && cred_info.timestamp.is_some());
+
+    Ok(PresentationVerificationResult {
+        valid,
+        attributes,
+        predicates,
+        revocation_checked,
+    })
+}
+
+#[cfg(test)]
+pub mod unit_tests {
+    use messages::{
+        decorators::thread::Thread,
+        msg_fields::protocols::present_proof::v1::{
+            present::{PresentationV1Content, PresentationV1Decorators},
+            request::{RequestPresentationV1Content, RequestPresentationV1Decorators},
+        },
+    };
+    use serde_json::Value;
+    use test_utils::{
+        constants::PROOF_JSON,
+        devsetup::SetupMocks,
+        mockdata::{mock_anoncreds::MockAnoncreds, mock_ledger::MockLedger},
+    };
+
+    use super::*;
+    use crate::handlers::util::make_attach_from_str;
+
+    fn _presentation_request() -> RequestPresentationV1 {
+        _presentation_request_for(json!({ "attr_name": { "name": "name" } }))
+    }
+
+    fn _presentation_request_for(requested_attributes: Value) -> RequestPresentationV1 {
+        let proof_req = json!({
+            "nonce": "123432421212",
+            "name": "proof_req_1",
+            "version": "0.1",
+            "requested_attributes": requested_attributes,
+            "requested_predicates": {}
+        })
+        .to_string();
+        RequestPresentationV1::builder()
+            .id("request-id".to_owned())
+            .content(
+                RequestPresentationV1Content::builder()
+                    .request_presentations_attach(vec![make_attach_from_str!(
+                        &proof_req,
+                        "libindy-request-presentation-0".to_owned()
+                    )])
+                    .build(),
+            )
+            .decorators(RequestPresentationV1Decorators::default())
+            .build()
+    }
+
+    fn _presentation(proof: &str) -> PresentationV1 {
+        PresentationV1::builder()
+            .id("presentation-id".to_owned())
+            .content(
+                PresentationV1Content::builder()
+                    .presentations_attach(vec![make_attach_from_str!(
+                        proof,
+                        "libindy-presentation-0".to_owned()
+                    )])
+                    .build(),
+            )
+            .decorators(
+                PresentationV1Decorators::builder()
+                    .thread(Thread::builder().thid("request-id".to_owned()).build())
+                    .build(),
+            )
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_verify_presentation_tampered_attribute_is_invalid() {
+        let _setup = SetupMocks::init();
+        let proof = json!({
+            "identifiers": [],
+            "requested_proof": {
+                "revealed_attrs": {
+                    "attr_name": { "sub_proof_index": 0, "raw": "Alice", "encoded": "123" }
+                },
+                "predicates": {}
+            }
+        })
+        .to_string();
+
+        let result = verify_presentation(
+            &MockLedger,
+            &MockAnoncreds,
+            &_presentation_request(),
+            &_presentation(&proof),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result,
+            PresentationVerificationResult {
+                valid: false,
+                attributes: vec![],
+                predicates: vec![],
+                revocation_checked: false,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_presentation_valid() {
+        let _setup = SetupMocks::init();
+        let presentation_request = _presentation_request_for(json!({
+            "zip_2": { "name": "zip" },
+            "height_1": { "name": "height" },
+            "self_attest_3": { "name": "self_attest" }
+        }));
+
+        let result = verify_presentation(
+            &MockLedger,
+            &MockAnoncreds,
+            &presentation_request,
+            &_presentation(PROOF_JSON),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result,
+            PresentationVerificationResult {
+                valid: true,
+                attributes: vec![
+                    "height_1".to_owned(),
+                    "self_attest_3".to_owned(),
+                    "zip_2".to_owned()
+                ],
+                predicates: vec![],
+                revocation_checked: false,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_presentation_malformed_attachment_fails() {
+        let _setup = SetupMocks::init();
+        let mut presentation = _presentation("{}");
+        presentation.content.presentations_attach = vec![];
+
+        let err = verify_presentation(
+            &MockLedger,
+            &MockAnoncreds,
+            &_presentation_request(),
+            &presentation,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.kind(), AriesVcxErrorKind::SerializationError);
+    }
+}
@@ -91,6 +91,40 @@ pub fn validate_proof_revealed_attributes(proof_json: &str) -> VcxResult<()> {
     Ok(())
 }
 
+/// Returns the referents of the requested attributes and predicates which the proof reports as
+/// satisfied, both sorted. Attributes include revealed, unrevealed and self attested ones as well
+/// as attribute groups.
+pub fn get_proof_referents(proof_json: &str) -> VcxResult<(Vec<String>, Vec<String>)> {
+    let proof: Value = serde_json::from_str(proof_json).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Cannot deserialize libndy proof: {}", err),
+        )
+    })?;
+    let requested_proof = &proof["requested_proof"];
+    let referents_of = |field: &str| -> Vec<String> {
+        requested_proof[field]
+            .as_object()
+            .map(|entries| entries.keys().cloned().collect())
+            .unwrap_or_default()
+    };
+
+    let mut attributes: Vec<String> = [
+        "revealed_attrs",
+        "revealed_attr_groups",
+        "unrevealed_attrs",
+        "self_attested_attrs",
+    ]
+    .iter()
+    .flat_map(|field| referents_of(field))
+    .collect();
+    attributes.sort();
+    let mut predicates = referents_of("predicates");
+    predicates.sort();
+
+    Ok((attributes, predicates))
+}
+
 pub async fn build_cred_defs_json_verifier(
     ledger: &impl AnoncredsLedgerRead,
     credential_data: &[CredInfoVerifier],
@@ -230,6 +264,29 @@ pub mod unit_tests {
 
     use super::*;
 
+    #[test]
+    fn test_get_proof_referents() {
+        let proof = json!({
+            "requested_proof": {
+                "revealed_attrs": { "attr_name": { "sub_proof_index": 0 } },
+                "revealed_attr_groups": { "attr_group": { "sub_proof_index": 0 } },
+                "self_attested_attrs": { "attr_self": "value" },
+                "unrevealed_attrs": {},
+                "predicates": { "predicate_age": { "sub_proof_index": 0 } }
+            }
+        })
+        .to_string();
+
+        let (attributes, predicates) = get_proof_referents(&proof).unwrap();
+        assert_eq!(attributes, vec!["attr_group", "attr_name", "attr_self"]);
+        assert_eq!(predicates, vec!["predicate_age"]);
+    }
+
+    #[test]
+    fn test_get_proof_referents_invalid_json() {
+        assert!(get_proof_referents("not a proof").is_err());
+    }
+
     #[tokio::test]
     async fn test_build_cred_defs_json_verifier_with_multiple_credentials() {
         let _setup = SetupMocks::init();
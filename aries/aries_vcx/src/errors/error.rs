@@ -137,6 +137,8 @@ pub enum AriesVcxErrorKind {
     UnknownError,
     #[error("Invalid DID")]
     InvalidDid,
+    #[error("No resolver is registered for the DID method")]
+    UnsupportedDidMethod,
     #[error("Invalid VERKEY")]
     InvalidVerkey,
     #[error("Invalid NONCE")]
@@ -147,6 +149,8 @@ pub enum AriesVcxErrorKind {
     SerializationError,
     #[error("Value needs to be base58")]
     NotBase58,
+    #[error("Value needs to be base64")]
+    NotBase64,
     #[error("Could not parse a value")]
     ParsingError,
 
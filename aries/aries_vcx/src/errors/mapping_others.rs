@@ -1,6 +1,7 @@
 use std::{num::ParseIntError, sync::PoisonError};
 
 use did_doc::schema::{types::uri::UriWrapperError, utils::error::DidDocumentLookupError};
+use did_resolver_registry::error::DidResolverRegistryError;
 use shared::errors::http_error::HttpError;
 
 use crate::{
@@ -59,7 +60,13 @@ impl From<did_peer::error::DidPeerError> for AriesVcxError {
 
 impl From<did_resolver::error::GenericError> for AriesVcxError {
     fn from(err: did_resolver::error::GenericError) -> Self {
-        AriesVcxError::from_msg(AriesVcxErrorKind::InvalidState, err.to_string())
+        let kind = match err.downcast_ref::<DidResolverRegistryError>() {
+            Some(DidResolverRegistryError::UnsupportedMethod(_)) => {
+                AriesVcxErrorKind::UnsupportedDidMethod
+            }
+            _ => AriesVcxErrorKind::InvalidState,
+        };
+        AriesVcxError::from_msg(kind, err.to_string())
     }
 }
 
@@ -92,3 +99,28 @@ impl From<ParseIntError> for AriesVcxError {
         AriesVcxError::from_msg(AriesVcxErrorKind::InvalidInput, err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_did_method_maps_to_distinct_kind() {
+        let err: did_resolver::error::GenericError = Box::new(
+            DidResolverRegistryError::UnsupportedMethod("web".to_string()),
+        );
+        assert_eq!(
+            AriesVcxError::from(err).kind(),
+            AriesVcxErrorKind::UnsupportedDidMethod
+        );
+    }
+
+    #[test]
+    fn test_other_resolution_errors_map_to_invalid_state() {
+        let err: did_resolver::error::GenericError = "resolution failed".into();
+        assert_eq!(
+            AriesVcxError::from(err).kind(),
+            AriesVcxErrorKind::InvalidState
+        );
+    }
+}
@@ -4,6 +4,7 @@ use base64::{engine::general_purpose, Engine};
 use messages::{
     decorators::attachment::{Attachment, AttachmentType},
     msg_fields::protocols::{
+        connection::Connection,
         cred_issuance::v1::offer_credential::OfferCredentialV1,
         out_of_band::{invitation::Invitation, OutOfBand},
         present_proof::v1::request::RequestPresentationV1,
@@ -12,8 +13,16 @@ use messages::{
 };
 use serde::Deserialize;
 use serde_json::Value;
+use url::Url;
 
-use crate::{errors::error::prelude::*, handlers::util::AttachmentId};
+use crate::{
+    errors::error::prelude::*,
+    handlers::util::{AnyInvitation, AttachmentId},
+    utils::base64::{STANDARD_LENIENT, URL_SAFE_LENIENT},
+};
+
+/// Query parameters an invitation can be carried in, in the order they are tried.
+const INVITATION_QUERY_PARAMS: [&str; 2] = ["oob", "c_i"];
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct OutOfBandReceiver {
@@ -75,6 +84,77 @@ impl Display for OutOfBandReceiver {
     }
 }
 
+/// Parses an invitation link into either an out-of-band or a legacy connection invitation.
+///
+/// The invitation is taken from the `oob` or `c_i` query parameter, also when the link is nested
+/// in another URL's query (e.g. a redirect). Input that is not a URL is treated as the bare
+/// base64 encoded invitation. Both base64url and standard base64 are accepted, padded or not.
+///
+/// Each step fails with its own error kind: [`AriesVcxErrorKind::InvalidUrl`] when no invitation
+/// parameter is found, [`AriesVcxErrorKind::NotBase64`] when the payload is not base64,
+/// [`AriesVcxErrorKind::InvalidJson`] when the decoded payload is not JSON and
+/// [`AriesVcxErrorKind::InvalidMessageFormat`] when it is JSON but not an invitation.
+pub fn parse_invitation_url(url: &str) -> VcxResult<AnyInvitation> {
+    let url = url.trim();
+    let encoded = match Url::parse(url) {
+        Ok(parsed) => find_encoded_invitation(&parsed).ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidUrl,
+                format!("No oob or c_i parameter found in invitation URL: {url}"),
+            )
+        })?,
+        Err(_) => url.to_string(),
+    };
+
+    let bytes = URL_SAFE_LENIENT
+        .decode(&encoded)
+        .or_else(|_| STANDARD_LENIENT.decode(&encoded))
+        .map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotBase64,
+                format!("Invitation is not base64 encoded: {err}"),
+            )
+        })?;
+
+    let message: AriesMessage = serde_json::from_slice(&bytes).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            format!("Decoded invitation is not valid JSON: {err}"),
+        )
+    })?;
+
+    match message {
+        AriesMessage::OutOfBand(OutOfBand::Invitation(invitation)) => {
+            Ok(AnyInvitation::Oob(invitation))
+        }
+        AriesMessage::Connection(Connection::Invitation(invitation)) => {
+            Ok(AnyInvitation::Con(invitation))
+        }
+        m => Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidMessageFormat,
+            format!("Expected an invitation message, but received: {m:?}"),
+        )),
+    }
+}
+
+fn find_encoded_invitation(url: &Url) -> Option<String> {
+    INVITATION_QUERY_PARAMS
+        .iter()
+        .find_map(|name| {
+            url.query_pairs()
+                .find(|(key, _)| key == *name)
+                // form decoding turns an unescaped '+' of standard base64 into a space
+                .map(|(_, value)| value.replace(' ', "+"))
+        })
+        .or_else(|| {
+            url.query_pairs().find_map(|(_, value)| {
+                Url::parse(&value)
+                    .ok()
+                    .and_then(|inner| find_encoded_invitation(&inner))
+            })
+        })
+}
+
 fn attachment_to_aries_message(attach: &Attachment) -> VcxResult<Option<AriesMessage>> {
     let AttachmentType::Base64(encoded_attach) = &attach.data.content else {
         return Err(AriesVcxError::from_msg(
@@ -136,3 +216,91 @@ fn attachment_to_aries_message(attach: &Attachment) -> VcxResult<Option<AriesMes
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _oob_invitation_json() -> String {
+        json!({
+            "@type": "https://didcomm.org/out-of-band/1.1/invitation",
+            "@id": "69212a3a-d068-4f9d-a2dd-4741bca89af3",
+            "services": ["did:sov:LjgpST2rjsoxYegQDRm7EL"]
+        })
+        .to_string()
+    }
+
+    fn _connection_invitation_json() -> String {
+        json!({
+            "@type": "https://didcomm.org/connections/1.0/invitation",
+            "@id": "12345678900987654321",
+            "label": "Alice",
+            "recipientKeys": ["8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K"],
+            "serviceEndpoint": "https://example.com/endpoint"
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_parse_invitation_url_oob() {
+        let encoded = general_purpose::URL_SAFE_NO_PAD.encode(_oob_invitation_json());
+        let url = format!("https://example.com/?oob={encoded}");
+
+        let invitation = parse_invitation_url(&url).unwrap();
+        assert!(matches!(invitation, AnyInvitation::Oob(_)));
+        assert_eq!(invitation.id(), "69212a3a-d068-4f9d-a2dd-4741bca89af3");
+    }
+
+    #[test]
+    fn test_parse_invitation_url_legacy_c_i() {
+        let encoded = general_purpose::STANDARD.encode(_connection_invitation_json());
+        let url = Url::parse_with_params("https://example.com/", &[("c_i", encoded)]).unwrap();
+
+        let invitation = parse_invitation_url(url.as_str()).unwrap();
+        assert!(matches!(invitation, AnyInvitation::Con(_)));
+        assert_eq!(invitation.id(), "12345678900987654321");
+    }
+
+    #[test]
+    fn test_parse_invitation_url_nested_in_redirect() {
+        let encoded = general_purpose::URL_SAFE.encode(_oob_invitation_json());
+        let inner = format!("https://example.com/?oob={encoded}");
+        let url = Url::parse_with_params(
+            "https://redirect.example.com/",
+            &[("utm_source", "qr"), ("next", inner.as_str())],
+        )
+        .unwrap();
+
+        let invitation = parse_invitation_url(url.as_str()).unwrap();
+        assert!(matches!(invitation, AnyInvitation::Oob(_)));
+    }
+
+    #[test]
+    fn test_parse_invitation_url_bare_base64() {
+        let encoded = general_purpose::URL_SAFE_NO_PAD.encode(_oob_invitation_json());
+
+        let invitation = parse_invitation_url(&encoded).unwrap();
+        assert!(matches!(invitation, AnyInvitation::Oob(_)));
+    }
+
+    #[test]
+    fn test_parse_invitation_url_missing_param() {
+        let err = parse_invitation_url("https://example.com/?foo=bar").unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidUrl);
+    }
+
+    #[test]
+    fn test_parse_invitation_url_malformed_base64() {
+        let err = parse_invitation_url("https://example.com/?oob=not*base64").unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::NotBase64);
+    }
+
+    #[test]
+    fn test_parse_invitation_url_invalid_json() {
+        let encoded = general_purpose::URL_SAFE_NO_PAD.encode("{not json");
+        let url = format!("https://example.com/?oob={encoded}");
+
+        let err = parse_invitation_url(&url).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+    }
+}
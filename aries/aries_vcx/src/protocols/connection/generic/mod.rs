@@ -203,7 +203,7 @@ impl GenericConnection {
             "No DidDoc present",
         ))?;
 
-        let msg = self.encrypt_message(wallet, message).await?.0;
+        let msg = self.encrypt_message(wallet, message).await?.into_bytes();
         let service_endpoint = did_doc.get_endpoint().ok_or_else(|| {
             AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, "No URL in DID Doc")
         })?;
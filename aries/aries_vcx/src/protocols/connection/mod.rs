@@ -133,7 +133,7 @@ where
     where
         T: Transport,
     {
-        let msg = self.encrypt_message(wallet, message).await?.0;
+        let msg = self.encrypt_message(wallet, message).await?.into_bytes();
         let service_endpoint = self.their_did_doc().get_endpoint().ok_or_else(|| {
             AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, "No URL in DID Doc")
         })?;
@@ -5,10 +5,14 @@ use did_doc::schema::did_doc::DidDocument;
 use did_parser_nom::Did;
 use did_peer::peer_did::{numalgos::numalgo4::Numalgo4, PeerDid};
 use did_resolver_registry::ResolverRegistry;
-use messages::msg_fields::protocols::did_exchange::{
-    complete::Complete, problem_report::ProblemReport, request::Request, response::Response,
+use messages::{
+    decorators::thread::ThreadGoalCode,
+    msg_fields::protocols::did_exchange::{
+        complete::Complete, problem_report::ProblemReport, request::Request, response::Response,
+    },
 };
 use public_key::Key;
+use shared::maybe_known::MaybeKnown;
 pub use thin_state::ThinState;
 
 use super::{requester::DidExchangeRequester, responder::DidExchangeResponder};
@@ -26,20 +30,20 @@ use crate::{
 mod conversions;
 mod thin_state;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GenericDidExchange {
     Requester(RequesterState),
     Responder(ResponderState),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RequesterState {
     RequestSent(DidExchangeRequester<RequestSent>),
     Completed(DidExchangeRequester<Completed>),
     Abandoned(DidExchangeRequester<Abandoned>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ResponderState {
     ResponseSent(DidExchangeResponder<ResponseSent>),
     Completed(DidExchangeResponder<Completed>),
@@ -88,6 +92,8 @@ impl GenericDidExchange {
         invitation_id: Option<String>,
         their_did: &Did,
         our_peer_did: &PeerDid<Numalgo4>,
+        goal: Option<String>,
+        goal_code: Option<MaybeKnown<ThreadGoalCode>>,
     ) -> Result<(Self, Request), AriesVcxError> {
         let TransitionResult { state, output } =
             DidExchangeRequester::<RequestSent>::construct_request(
@@ -95,6 +101,8 @@ impl GenericDidExchange {
                 invitation_id,
                 their_did,
                 our_peer_did,
+                goal,
+                goal_code,
             )
             .await?;
         Ok((
@@ -268,3 +276,114 @@ impl GenericDidExchange {
         }
     }
 }
+
+#[cfg(test)]
+mod did_exchange_serde_tests {
+    use super::*;
+    use crate::protocols::did_exchange::state_machine::{
+        requester::DidExchangeRequester, responder::DidExchangeResponder,
+    };
+
+    const REQUEST_ID: &str = "d4b1b4b5-2b5e-4b9e-9d3c-57a0e0a1f0aa";
+
+    fn our_did_doc() -> DidDocument {
+        DidDocument::new(Did::parse("did:example:our".into()).unwrap())
+    }
+
+    fn their_did_doc() -> DidDocument {
+        DidDocument::new(Did::parse("did:example:their".into()).unwrap())
+    }
+
+    fn serde_roundtrip(did_exchange: &GenericDidExchange) -> GenericDidExchange {
+        let serialized = serde_json::to_string(did_exchange).unwrap();
+        let deserialized: GenericDidExchange = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(
+            serde_json::to_value(did_exchange).unwrap(),
+            serde_json::to_value(&deserialized).unwrap()
+        );
+        assert_eq!(
+            deserialized.our_did_document().id().to_string(),
+            "did:example:our"
+        );
+        assert_eq!(
+            deserialized.their_did_doc().id().to_string(),
+            "did:example:their"
+        );
+        deserialized
+    }
+
+    #[test]
+    fn test_serde_request_sent() {
+        let did_exchange: GenericDidExchange = DidExchangeRequester::from_parts(
+            RequestSent {
+                request_id: REQUEST_ID.to_string(),
+            },
+            their_did_doc(),
+            our_did_doc(),
+        )
+        .into();
+
+        let deserialized = serde_roundtrip(&did_exchange);
+
+        match deserialized {
+            GenericDidExchange::Requester(RequesterState::RequestSent(state)) => {
+                assert_eq!(state.get_thread_id(), REQUEST_ID)
+            }
+            other => panic!("Unexpected state after deserialization: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_serde_response_sent() {
+        let did_exchange: GenericDidExchange = DidExchangeResponder::from_parts(
+            ResponseSent {
+                request_id: REQUEST_ID.to_string(),
+            },
+            their_did_doc(),
+            our_did_doc(),
+        )
+        .into();
+
+        let deserialized = serde_roundtrip(&did_exchange);
+
+        match deserialized {
+            GenericDidExchange::Responder(ResponderState::ResponseSent(state)) => {
+                assert_eq!(state.get_thread_id(), REQUEST_ID)
+            }
+            other => panic!("Unexpected state after deserialization: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_serde_completed() {
+        let requester: GenericDidExchange = DidExchangeRequester::from_parts(
+            Completed {
+                request_id: REQUEST_ID.to_string(),
+            },
+            their_did_doc(),
+            our_did_doc(),
+        )
+        .into();
+        let responder: GenericDidExchange = DidExchangeResponder::from_parts(
+            Completed {
+                request_id: REQUEST_ID.to_string(),
+            },
+            their_did_doc(),
+            our_did_doc(),
+        )
+        .into();
+
+        match serde_roundtrip(&requester) {
+            GenericDidExchange::Requester(RequesterState::Completed(state)) => {
+                assert_eq!(state.get_thread_id(), REQUEST_ID)
+            }
+            other => panic!("Unexpected state after deserialization: {:?}", other),
+        }
+        match serde_roundtrip(&responder) {
+            GenericDidExchange::Responder(ResponderState::Completed(state)) => {
+                assert_eq!(state.get_thread_id(), REQUEST_ID)
+            }
+            other => panic!("Unexpected state after deserialization: {:?}", other),
+        }
+    }
+}
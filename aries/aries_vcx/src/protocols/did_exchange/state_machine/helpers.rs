@@ -75,7 +75,31 @@ pub async fn create_peer_did_4(
     routing_keys: Vec<String>,
 ) -> Result<(PeerDid<Numalgo4>, Key), AriesVcxError> {
     let key_enc = generate_keypair(wallet, KeyType::Ed25519).await?;
+    let peer_did = build_peer_did_4(&key_enc, service_endpoint, routing_keys)?;
+    Ok((peer_did, key_enc))
+}
+
+/// Like [`create_peer_did_4`], but the key is derived from `seed` and stored in the wallet, so
+/// a test can pin the resulting DID and key while still encrypting and decrypting with them.
+/// Only available in test builds; production code always uses a randomly generated key.
+#[cfg(test)]
+pub(crate) async fn create_peer_did_4_from_seed(
+    wallet: &impl BaseWallet,
+    seed: &str,
+    service_endpoint: Url,
+    routing_keys: Vec<String>,
+) -> Result<(PeerDid<Numalgo4>, Key), AriesVcxError> {
+    let did_data = wallet.create_and_store_my_did(Some(seed), None).await?;
+    let key_enc = did_data.verkey().clone();
+    let peer_did = build_peer_did_4(&key_enc, service_endpoint, routing_keys)?;
+    Ok((peer_did, key_enc))
+}
 
+fn build_peer_did_4(
+    key_enc: &Key,
+    service_endpoint: Url,
+    routing_keys: Vec<String>,
+) -> Result<PeerDid<Numalgo4>, AriesVcxError> {
     let service: Service = ServiceDidCommV1::new(
         Uri::new("#0")?,
         service_endpoint,
@@ -107,7 +131,7 @@ pub async fn create_peer_did_4(
     let peer_did = PeerDid::<Numalgo4>::new(construction_did_doc)?;
     info!("Created peer did: {peer_did}");
 
-    Ok((peer_did, key_enc))
+    Ok(peer_did)
 }
 
 pub(crate) fn ddo_to_attach(ddo: DidDocument) -> Result<Attachment, AriesVcxError> {
@@ -200,3 +224,36 @@ where
         state,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_utils::devsetup::build_setup_profile;
+
+    use super::*;
+    use crate::utils::didcomm_utils::resolve_base58_key_agreement;
+
+    #[tokio::test]
+    async fn test_create_peer_did_4_from_seed() {
+        let setup = build_setup_profile().await;
+
+        let (peer_did, key) = create_peer_did_4_from_seed(
+            &setup.wallet,
+            "00000000000000000000000000Peer4A",
+            "http://example.org".parse().unwrap(),
+            vec![],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(key.base58(), "AZd91gNZdEV4u8DnMDvgFd2xypn4BA81rXharEBP3bdU");
+        assert_eq!(
+            peer_did.short_form().to_string(),
+            "did:peer:4z84UiPbmJdcUf2r7Ga6Es3UPcYt9QSVuDrdHaNmVmPm83ebWHGH6N5SLusG3Q634DUgtmFkRiYAWxvcVLytFB58bacJ"
+        );
+        let did_doc = peer_did.resolve_did_doc().unwrap();
+        assert_eq!(
+            resolve_base58_key_agreement(&did_doc).unwrap(),
+            key.base58()
+        );
+    }
+}
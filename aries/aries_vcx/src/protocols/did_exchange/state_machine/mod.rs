@@ -40,7 +40,7 @@ impl<I, S: ThreadId> DidExchange<I, S> {
         problem_code: Option<ProblemCode>,
     ) -> TransitionResult<DidExchange<I, Abandoned>, ProblemReport> {
         let content = ProblemReportContent::builder()
-            .problem_code(problem_code)
+            .problem_code(problem_code.clone())
             .explain(Some(reason.clone()))
             .build();
         let decorators = ProblemReportDecorators::builder()
@@ -62,6 +62,7 @@ impl<I, S: ThreadId> DidExchange<I, S> {
                 state: Abandoned {
                     reason,
                     request_id: self.state.thread_id().to_string(),
+                    problem_code,
                 },
                 initiation_type: PhantomData,
                 our_did_document: self.our_did_document,
@@ -79,6 +80,7 @@ impl<I, S: ThreadId> DidExchange<I, S> {
             state: Abandoned {
                 reason: problem_report.content.explain.unwrap_or_default(),
                 request_id: self.state.thread_id().to_string(),
+                problem_code: problem_report.content.problem_code,
             },
             initiation_type: PhantomData,
             our_did_document: self.our_did_document,
@@ -87,6 +89,17 @@ impl<I, S: ThreadId> DidExchange<I, S> {
     }
 }
 
+impl<I> DidExchange<I, Abandoned> {
+    pub fn reason(&self) -> &str {
+        &self.state.reason
+    }
+
+    /// Problem code of the problem report which abandoned the exchange, whether sent or received.
+    pub fn problem_code(&self) -> Option<&ProblemCode> {
+        self.state.problem_code.as_ref()
+    }
+}
+
 impl<I, S> DidExchange<I, S> {
     pub fn from_parts(
         state: S,
@@ -111,3 +124,70 @@ impl<I, S> DidExchange<I, S> {
         &self.their_did_document
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use did_parser_nom::Did;
+
+    use super::*;
+    use crate::protocols::did_exchange::{
+        state_machine::requester::DidExchangeRequester,
+        states::requester::request_sent::RequestSent,
+    };
+
+    fn request_sent_requester() -> DidExchangeRequester<RequestSent> {
+        DidExchangeRequester::from_parts(
+            RequestSent {
+                request_id: "request-id".to_string(),
+            },
+            DidDocument::new(Did::parse("did:example:their".into()).unwrap()),
+            DidDocument::new(Did::parse("did:example:our".into()).unwrap()),
+        )
+    }
+
+    #[test]
+    fn test_receive_problem_report_keeps_reason_and_code() {
+        let problem_report = ProblemReport::builder()
+            .id("problem-report-id".to_string())
+            .content(
+                ProblemReportContent::builder()
+                    .problem_code(Some(ProblemCode::RequestNotAccepted))
+                    .explain(Some("Unsupported DID method".to_string()))
+                    .build(),
+            )
+            .decorators(ProblemReportDecorators::new(
+                Thread::builder().thid("request-id".to_string()).build(),
+            ))
+            .build();
+
+        let abandoned = request_sent_requester().receive_problem_report(problem_report);
+
+        assert_eq!(abandoned.get_thread_id(), "request-id");
+        assert_eq!(abandoned.reason(), "Unsupported DID method");
+        assert_eq!(
+            abandoned.problem_code(),
+            Some(&ProblemCode::RequestNotAccepted)
+        );
+    }
+
+    #[test]
+    fn test_fail_keeps_sent_problem_code() {
+        let TransitionResult {
+            state: abandoned,
+            output: problem_report,
+        } = request_sent_requester().fail(
+            "Response not accepted".to_string(),
+            Some(ProblemCode::ResponseNotAccepted),
+        );
+
+        assert_eq!(abandoned.reason(), "Response not accepted");
+        assert_eq!(
+            abandoned.problem_code(),
+            Some(&ProblemCode::ResponseNotAccepted)
+        );
+        assert_eq!(
+            problem_report.content.problem_code,
+            Some(ProblemCode::ResponseNotAccepted)
+        );
+    }
+}
@@ -18,7 +18,12 @@ use uuid::Uuid;
 
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
 
-pub fn construct_request(invitation_id: Option<String>, our_did: String) -> Request {
+pub fn construct_request(
+    invitation_id: Option<String>,
+    our_did: String,
+    goal: Option<String>,
+    goal_code: Option<MaybeKnown<ThreadGoalCode>>,
+) -> Request {
     let msg_id = Uuid::new_v4().to_string();
     let thid = msg_id.clone();
     let thread = match invitation_id {
@@ -33,8 +38,8 @@ pub fn construct_request(invitation_id: Option<String>, our_did: String) -> Requ
         .label("".into())
         .did(our_did)
         .did_doc(None)
-        .goal(Some("To establish a connection".into())) // Rejected if non-empty by acapy
-        .goal_code(Some(MaybeKnown::Known(ThreadGoalCode::AriesRelBuild))) // Rejected if non-empty by acapy
+        .goal(goal)
+        .goal_code(goal_code)
         .build();
     Request::builder()
         .id(msg_id)
@@ -89,3 +94,37 @@ pub fn invitation_get_first_did_service(invitation: &Invitation) -> VcxResult<Di
         "Invitation does not contain did service",
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_construct_request_without_goal() {
+        let request = construct_request(None, "did:example:123".into(), None, None);
+        assert_eq!(request.content.goal, None);
+        assert_eq!(request.content.goal_code, None);
+    }
+
+    #[test]
+    fn test_construct_request_with_goal() {
+        let request = construct_request(
+            Some("invitation-id".into()),
+            "did:example:123".into(),
+            Some("To establish a relationship".into()),
+            Some(MaybeKnown::Known(ThreadGoalCode::AriesRelBuild)),
+        );
+        assert_eq!(
+            request.content.goal,
+            Some("To establish a relationship".to_owned())
+        );
+        assert_eq!(
+            request.content.goal_code,
+            Some(MaybeKnown::Known(ThreadGoalCode::AriesRelBuild))
+        );
+        assert_eq!(
+            request.decorators.thread.unwrap().pthid,
+            Some("invitation-id".to_owned())
+        );
+    }
+}
@@ -4,9 +4,13 @@ use did_parser_nom::Did;
 use did_peer::peer_did::{numalgos::numalgo4::Numalgo4, PeerDid};
 use did_resolver::traits::resolvable::resolution_output::DidResolutionOutput;
 use did_resolver_registry::ResolverRegistry;
-use messages::msg_fields::protocols::did_exchange::{
-    complete::Complete as CompleteMessage, request::Request, response::Response,
+use messages::{
+    decorators::thread::ThreadGoalCode,
+    msg_fields::protocols::did_exchange::{
+        complete::Complete as CompleteMessage, request::Request, response::Response,
+    },
 };
+use shared::maybe_known::MaybeKnown;
 
 use super::DidExchangeRequester;
 use crate::{
@@ -27,6 +31,8 @@ impl DidExchangeRequester<RequestSent> {
         invitation_id: Option<String>,
         their_did: &Did,
         our_peer_did: &PeerDid<Numalgo4>,
+        goal: Option<String>,
+        goal_code: Option<MaybeKnown<ThreadGoalCode>>,
     ) -> Result<TransitionResult<Self, Request>, AriesVcxError> {
         info!(
             "DidExchangeRequester<RequestSent>::construct_request >> their_did: {}, our_peer_did: \
@@ -38,7 +44,12 @@ impl DidExchangeRequester<RequestSent> {
             .await?
             .did_document;
         let our_did_document = our_peer_did.resolve_did_doc()?;
-        let request = construct_request(invitation_id.clone(), our_peer_did.to_string());
+        let request = construct_request(
+            invitation_id.clone(),
+            our_peer_did.to_string(),
+            goal,
+            goal_code,
+        );
 
         info!(
             "DidExchangeRequester<RequestSent>::construct_request << prepared request: {}",
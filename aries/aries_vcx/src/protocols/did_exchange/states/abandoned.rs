@@ -1,9 +1,12 @@
+use messages::msg_fields::protocols::did_exchange::problem_report::ProblemCode;
+
 use super::traits::ThreadId;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Abandoned {
     pub reason: String,
     pub request_id: String,
+    pub problem_code: Option<ProblemCode>,
 }
 
 impl ThreadId for Abandoned {
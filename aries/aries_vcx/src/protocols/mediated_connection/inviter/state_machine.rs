@@ -326,18 +326,25 @@ impl SmMediatedConnectionInviter {
         Ok(Self { state, ..self })
     }
 
+    /// Sends the signed response and moves to `Responded`. If sending fails, the inviter is
+    /// returned in `Requested` alongside the error, still holding the signed response and the
+    /// invitee's DID document, so the send can be retried.
     pub async fn handle_send_response(
         self,
         send_message: SendClosureConnection<'_>,
-    ) -> VcxResult<Self> {
+    ) -> Result<Self, (Self, AriesVcxError)> {
         let state = match self.state {
             MediatedInviterFullState::Requested(state) => {
-                send_message(
+                if let Err(err) = send_message(
                     state.signed_response.clone().into(),
                     self.pairwise_info.pw_vk.clone(),
                     state.did_doc.clone(),
                 )
-                .await?;
+                .await
+                {
+                    let state = MediatedInviterFullState::Requested(state);
+                    return Err((Self { state, ..self }, err));
+                }
                 MediatedInviterFullState::Responded(state.into())
             }
             _ => self.state,
@@ -417,6 +424,90 @@ impl SmMediatedConnectionInviter {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use messages::msg_fields::protocols::connection::response::ConnectionSignature;
+
+    use super::*;
+
+    fn _requested_inviter() -> SmMediatedConnectionInviter {
+        let thread_id = Uuid::new_v4().to_string();
+        let signed_response = Response::builder()
+            .id(Uuid::new_v4().to_string())
+            .content(
+                ResponseContent::builder()
+                    .connection_sig(ConnectionSignature::new(
+                        "signature".to_owned(),
+                        "sig_data".to_owned(),
+                        "signer".to_owned(),
+                    ))
+                    .build(),
+            )
+            .decorators(
+                ResponseDecorators::builder()
+                    .thread(Thread::builder().thid(thread_id.clone()).build())
+                    .build(),
+            )
+            .build();
+        let state = RequestedState {
+            signed_response,
+            did_doc: AriesDidDoc::default(),
+            thread_id: thread_id.clone(),
+        };
+        SmMediatedConnectionInviter::from(
+            "test_source_id".to_owned(),
+            thread_id,
+            PairwiseInfo::default(),
+            MediatedInviterFullState::Requested(state),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_handle_send_response_success() {
+        let send_message: SendClosureConnection =
+            Box::new(|_: AriesMessage, _: String, _: AriesDidDoc| Box::pin(async { Ok(()) }));
+
+        let Ok(inviter) = _requested_inviter()
+            .handle_send_response(send_message)
+            .await
+        else {
+            panic!("Expected sending the response to succeed");
+        };
+
+        assert_eq!(inviter.get_state(), MediatedInviterState::Responded);
+    }
+
+    #[tokio::test]
+    async fn test_handle_send_response_failure_keeps_requested() {
+        let send_message: SendClosureConnection =
+            Box::new(|_: AriesMessage, _: String, _: AriesDidDoc| {
+                Box::pin(async {
+                    Err(AriesVcxError::from_msg(
+                        AriesVcxErrorKind::PostMessageFailed,
+                        "Endpoint unreachable",
+                    ))
+                })
+            });
+
+        let Err((inviter, err)) = _requested_inviter()
+            .handle_send_response(send_message)
+            .await
+        else {
+            panic!("Expected sending the response to fail");
+        };
+
+        assert_eq!(err.kind(), AriesVcxErrorKind::PostMessageFailed);
+        assert_eq!(inviter.get_state(), MediatedInviterState::Requested);
+
+        let send_message: SendClosureConnection =
+            Box::new(|_: AriesMessage, _: String, _: AriesDidDoc| Box::pin(async { Ok(()) }));
+        let Ok(inviter) = inviter.handle_send_response(send_message).await else {
+            panic!("Expected the retried send to succeed");
+        };
+        assert_eq!(inviter.get_state(), MediatedInviterState::Responded);
+    }
+}
+
 // #[cfg(test)]
 // pub mod unit_tests {
 //     use messages::concepts::ack::test_utils::_ack;
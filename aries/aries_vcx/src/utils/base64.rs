@@ -11,3 +11,7 @@ pub const LENIENT_PAD: GeneralPurposeConfig =
 /// A [GeneralPurpose] engine using the [alphabet::URL_SAFE] base64 alphabet and
 /// [DecodePaddingMode::Indifferent] config to decode both padded and unpadded.
 pub const URL_SAFE_LENIENT: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, LENIENT_PAD);
+
+/// A [GeneralPurpose] engine using the [alphabet::STANDARD] base64 alphabet and
+/// [DecodePaddingMode::Indifferent] config to decode both padded and unpadded.
+pub const STANDARD_LENIENT: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, LENIENT_PAD);
@@ -1,8 +1,14 @@
 use did_doc::schema::{
-    did_doc::DidDocument, service::service_key_kind::ServiceKeyKind, types::uri::Uri,
-    verification_method::VerificationMethodType,
+    did_doc::DidDocument,
+    service::service_key_kind::ServiceKeyKind,
+    types::uri::Uri,
+    verification_method::{
+        PublicKeyField, VerificationMethod, VerificationMethodKind, VerificationMethodType,
+    },
 };
-use public_key::Key;
+use did_key::DidKey;
+use did_parser_nom::DidUrl;
+use public_key::{Key, KeyType};
 
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
 
@@ -27,10 +33,23 @@ fn resolve_service_key_to_typed_key(
             })?;
             Ok(key)
         }
-        ServiceKeyKind::Value(value) => Ok(Key::new(
-            value.as_bytes().to_vec(),
-            public_key::KeyType::Ed25519,
-        )?),
+        ServiceKeyKind::Value(value) => Ok(Key::from_base58(value, KeyType::Ed25519)?),
+    }
+}
+
+/// DIDComm V1 envelopes can only be addressed to Ed25519 keys; any other key would yield an
+/// envelope the recipient cannot open.
+fn ensure_ed25519(key: Key) -> VcxResult<Key> {
+    match *key.key_type() {
+        KeyType::Ed25519 => Ok(key),
+        key_type => Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidState,
+            format!(
+                "Recipient key {} is of type {:?}, only Ed25519 keys are supported",
+                key.base58(),
+                key_type
+            ),
+        )),
     }
 }
 
@@ -45,6 +64,85 @@ pub fn resolve_base58_key_agreement(did_document: &DidDocument) -> VcxResult<Str
     Ok(key_base58.public_key()?.base58())
 }
 
+/// Resolves the key a DIDComm V1 message for `did_document` should be encrypted for. DIDComm V1
+/// envelopes are addressed to Ed25519 keys, so an Ed25519 key agreement key is preferred.
+/// Failing that, the first Ed25519 verification method is used; this covers numalgo 2 peer DIDs,
+/// whose key agreement keys are X25519 while the `V` keys are Ed25519.
+fn resolve_recipient_key(did_document: &DidDocument) -> VcxResult<Key> {
+    let ed25519_types = [
+        VerificationMethodType::Ed25519VerificationKey2018,
+        VerificationMethodType::Ed25519VerificationKey2020,
+    ];
+    if let Ok(key_agreement) = did_document.get_key_agreement_of_type(&ed25519_types) {
+        return Ok(key_agreement.public_key()?);
+    }
+    let verification_method = did_document
+        .verification_method()
+        .iter()
+        .find(|vm| ed25519_types.contains(vm.verification_method_type()))
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!(
+                    "No Ed25519 key usable as recipient key found in did document {}",
+                    did_document.id()
+                ),
+            )
+        })?;
+    Ok(verification_method.public_key()?)
+}
+
+fn resolve_verification_method_keys(
+    did_document: &DidDocument,
+    methods: &[VerificationMethodKind],
+) -> VcxResult<Vec<String>> {
+    let mut keys = Vec::new();
+    for method in methods {
+        let verification_method = match method {
+            VerificationMethodKind::Resolved(verification_method) => verification_method,
+            VerificationMethodKind::Resolvable(reference) => {
+                did_document.dereference_key(reference).ok_or_else(|| {
+                    AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidState,
+                        format!("Unable to dereference key: {}", reference),
+                    )
+                })?
+            }
+        };
+        keys.push(verification_method.public_key()?.base58());
+    }
+    Ok(keys)
+}
+
+/// Returns all key agreement keys of the did document in base58, in document order. Keys
+/// referenced by id are dereferenced against the document's verification methods.
+pub fn key_agreement_keys(did_document: &DidDocument) -> VcxResult<Vec<String>> {
+    resolve_verification_method_keys(did_document, did_document.key_agreement())
+}
+
+/// Returns all authentication keys of the did document in base58, in document order. Keys
+/// referenced by id are dereferenced against the document's verification methods.
+pub fn authentication_keys(did_document: &DidDocument) -> VcxResult<Vec<String>> {
+    resolve_verification_method_keys(did_document, did_document.authentication())
+}
+
+/// Resolves the recipient keys listed on the service identified by `service_id`.
+///
+/// If the service lists no recipient keys, either by omitting the field or by leaving it empty
+/// (as is the case for peer DID documents), an Ed25519 key of the did document is used instead,
+/// see [`resolve_recipient_key`]. An error is returned if a listed key is not an Ed25519 key, or
+/// if the document holds no usable key at all.
+pub fn get_recipient_keys(their_did_doc: &DidDocument, service_id: &Uri) -> VcxResult<Vec<Key>> {
+    let service = their_did_doc.get_service_by_id(service_id)?;
+    match service.extra_field_recipient_keys() {
+        Ok(recipient_keys) if !recipient_keys.is_empty() => recipient_keys
+            .iter()
+            .map(|key| ensure_ed25519(resolve_service_key_to_typed_key(key, their_did_doc)?))
+            .collect(),
+        _ => Ok(vec![resolve_recipient_key(their_did_doc)?]),
+    }
+}
+
 pub fn get_routing_keys(their_did_doc: &DidDocument, service_id: &Uri) -> VcxResult<Vec<String>> {
     let service = their_did_doc.get_service_by_id(service_id)?;
     match service.extra_field_routing_keys() {
@@ -59,3 +157,221 @@ pub fn get_routing_keys(their_did_doc: &DidDocument, service_id: &Uri) -> VcxRes
         Err(_err) => Ok(Vec::new()),
     }
 }
+
+/// Builds a minimal did:key document for a single Ed25519 key, with that key as its only key
+/// agreement key. It can stand in as the sender did document when encrypting for a counterparty
+/// if the sender has no resolvable DID (e.g. an ephemeral key used for a first message).
+///
+/// The document contains no services. A counterparty receiving a message encrypted with it
+/// learns the sender's key, but has no endpoint to reply to unless one is provided out of band.
+///
+/// Returns an `InvalidInput` error if `key` is not an Ed25519 key.
+pub fn did_doc_from_key(key: &Key) -> VcxResult<DidDocument> {
+    if *key.key_type() != KeyType::Ed25519 {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidInput,
+            format!(
+                "Unable to build did document from key {}, expected an Ed25519 key but got {:?}",
+                key.base58(),
+                key.key_type()
+            ),
+        ));
+    }
+    let did_key = DidKey::try_from(key.clone())?;
+    let verification_method = VerificationMethod::builder()
+        .id(DidUrl::parse(format!("{}#{}", did_key, key.fingerprint()))?)
+        .controller(did_key.did().clone())
+        .verification_method_type(VerificationMethodType::Ed25519VerificationKey2018)
+        .public_key(PublicKeyField::Base58 {
+            public_key_base58: key.base58(),
+        })
+        .build();
+
+    let mut did_doc = DidDocument::new(did_key.did().clone());
+    did_doc.add_verification_method(verification_method.clone());
+    did_doc.add_key_agreement_object(verification_method);
+    Ok(did_doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use did_doc::schema::service::{typed::didcommv1::ServiceDidCommV1, Service};
+    use did_parser_nom::Did;
+    use did_peer::resolver::PeerDidResolver;
+    use did_resolver::traits::resolvable::DidResolvable;
+    use did_resolver_key::DidKeyResolver;
+
+    use super::*;
+
+    #[test]
+    fn test_did_doc_from_key() {
+        let key = Key::from_base58(
+            "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K",
+            KeyType::Ed25519,
+        )
+        .unwrap();
+
+        let did_doc = did_doc_from_key(&key).unwrap();
+
+        assert_eq!(
+            did_doc.id().to_string(),
+            format!("did:key:{}", key.fingerprint())
+        );
+        assert_eq!(
+            resolve_base58_key_agreement(&did_doc).unwrap(),
+            "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K"
+        );
+    }
+
+    #[test]
+    fn test_did_doc_from_key_rejects_non_ed25519() {
+        let key = Key::from_base58(
+            "JhNWeSVLMYccCk7iopQW4guaSJTojqpMEELgSLhKwRr",
+            KeyType::X25519,
+        )
+        .unwrap();
+
+        let err = did_doc_from_key(&key).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn test_get_keys_from_numalgo2_peer_did() {
+        let did = "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.\
+                   Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.\
+                   Vz6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg.\
+                   SeyJpZCI6IiNzZXJ2aWNlLTAiLCJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCJ9"
+            .parse()
+            .unwrap();
+        let did_doc = PeerDidResolver::new()
+            .resolve(&did, &Default::default())
+            .await
+            .unwrap()
+            .did_document;
+
+        let service = did_doc.service().first().unwrap();
+        assert_eq!(
+            service.service_endpoint().to_string(),
+            "https://example.com/endpoint"
+        );
+        let expected_key =
+            Key::from_fingerprint("z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V")
+                .unwrap()
+                .base58();
+        assert_eq!(
+            get_recipient_keys(&did_doc, service.id())
+                .unwrap()
+                .iter()
+                .map(Key::base58)
+                .collect::<Vec<_>>(),
+            vec![expected_key]
+        );
+        assert!(get_routing_keys(&did_doc, service.id()).unwrap().is_empty());
+        assert_eq!(
+            key_agreement_keys(&did_doc).unwrap(),
+            vec!["JhNWeSVLMYccCk7iopQW4guaSJTojqpMEELgSLhKwRr".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_key_agreement_and_authentication_keys() {
+        let key = Key::from_base58(
+            "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K",
+            KeyType::Ed25519,
+        )
+        .unwrap();
+        let mut did_doc = did_doc_from_key(&key).unwrap();
+
+        assert_eq!(key_agreement_keys(&did_doc).unwrap(), vec![key.base58()]);
+        assert!(authentication_keys(&did_doc).unwrap().is_empty());
+
+        let verification_method_id = did_doc.verification_method()[0].id().clone();
+        did_doc.add_authentication_ref(verification_method_id);
+        assert_eq!(authentication_keys(&did_doc).unwrap(), vec![key.base58()]);
+
+        did_doc.add_authentication_ref(DidUrl::from_fragment("unknown".to_string()).unwrap());
+        assert!(authentication_keys(&did_doc).is_err());
+    }
+
+    fn _did_doc_with_service(recipient_keys: Vec<ServiceKeyKind>) -> (DidDocument, Uri) {
+        let did: Did = "did:example:123".parse().unwrap();
+        let mut did_doc = DidDocument::new(did.clone());
+        did_doc.add_verification_method(
+            VerificationMethod::builder()
+                .id(DidUrl::parse("did:example:123#key-x25519".to_string()).unwrap())
+                .controller(did)
+                .verification_method_type(VerificationMethodType::X25519KeyAgreementKey2019)
+                .public_key(PublicKeyField::Base58 {
+                    public_key_base58: "JhNWeSVLMYccCk7iopQW4guaSJTojqpMEELgSLhKwRr".to_string(),
+                })
+                .build(),
+        );
+        let service: Service = ServiceDidCommV1::new(
+            Uri::new("#0").unwrap(),
+            "https://example.com/endpoint".parse().unwrap(),
+            0,
+            recipient_keys,
+            vec![],
+        )
+        .try_into()
+        .unwrap();
+        let service_id = service.id().clone();
+        did_doc.add_service(service);
+        (did_doc, service_id)
+    }
+
+    #[test]
+    fn test_get_recipient_keys_rejects_x25519_reference() {
+        let (did_doc, service_id) = _did_doc_with_service(vec![ServiceKeyKind::Reference(
+            DidUrl::from_fragment("key-x25519".to_string()).unwrap(),
+        )]);
+
+        let err = get_recipient_keys(&did_doc, &service_id).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidState);
+    }
+
+    #[test]
+    fn test_get_recipient_keys_from_base58_value() {
+        let (did_doc, service_id) = _did_doc_with_service(vec![ServiceKeyKind::Value(
+            "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K".to_string(),
+        )]);
+
+        let recipient_keys = get_recipient_keys(&did_doc, &service_id).unwrap();
+        assert_eq!(recipient_keys.len(), 1);
+        assert_eq!(recipient_keys[0].key_type(), &KeyType::Ed25519);
+        assert_eq!(
+            recipient_keys[0].base58(),
+            "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_recipient_keys_from_resolved_did_key() {
+        let did: Did = "did:key:z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th"
+            .parse()
+            .unwrap();
+        let mut did_doc = DidKeyResolver::new()
+            .resolve(&did, &())
+            .await
+            .unwrap()
+            .did_document;
+        let service: Service = ServiceDidCommV1::new(
+            Uri::new("#0").unwrap(),
+            "https://example.com/endpoint".parse().unwrap(),
+            0,
+            vec![],
+            vec![],
+        )
+        .try_into()
+        .unwrap();
+        let service_id = service.id().clone();
+        did_doc.add_service(service);
+
+        let expected_key =
+            Key::from_fingerprint("z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th").unwrap();
+        assert_eq!(
+            get_recipient_keys(&did_doc, &service_id).unwrap(),
+            vec![expected_key]
+        );
+    }
+}
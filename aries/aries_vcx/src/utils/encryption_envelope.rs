@@ -1,4 +1,5 @@
 use aries_vcx_wallet::wallet::base_wallet::BaseWallet;
+use base64::Engine;
 use did_doc::schema::{did_doc::DidDocument, types::uri::Uri};
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
 use messages::{
@@ -6,17 +7,58 @@ use messages::{
     AriesMessage,
 };
 use public_key::{Key, KeyType};
+use serde_json::Value;
+pub use shared::http_client::DIDCOMM_V1_ENVELOPE_CONTENT_TYPE;
 use uuid::Uuid;
 
 use crate::{
     errors::error::prelude::*,
-    utils::didcomm_utils::{get_routing_keys, resolve_base58_key_agreement},
+    utils::{
+        base64::URL_SAFE_LENIENT,
+        didcomm_utils::{get_recipient_keys, get_routing_keys, resolve_base58_key_agreement},
+    },
 };
 
 #[derive(Debug)]
 pub struct EncryptionEnvelope(pub Vec<u8>);
 
+/// Whether an envelope reveals its sender to the recipient (authcrypt) or not (anoncrypt).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionMode {
+    Authcrypt,
+    Anoncrypt,
+}
+
 impl EncryptionEnvelope {
+    /// Media type which should be used when delivering the envelope, e.g. as the HTTP
+    /// `Content-Type` header.
+    pub fn content_type(&self) -> &'static str {
+        DIDCOMM_V1_ENVELOPE_CONTENT_TYPE
+    }
+
+    /// Consumes the envelope, returning the encrypted bytes together with their media type, for
+    /// callers delivering the envelope through their own transport.
+    pub fn into_parts(self) -> (Vec<u8>, &'static str) {
+        let content_type = self.content_type();
+        (self.0, content_type)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub async fn create_from_legacy(
         wallet: &impl BaseWallet,
         data: &[u8],
@@ -55,6 +97,10 @@ impl EncryptionEnvelope {
     ///   message
     /// * `their_service_id` - Id of service where message will be sent. The counterparty did
     ///   document must contain Service object identified with such value.
+    ///
+    /// The message is encrypted for every recipient key listed on the service, so that any of
+    /// the counterparty's devices can decrypt it. The message is authcrypted, revealing our key
+    /// to the counterparty; use [`EncryptionEnvelope::create_anoncrypt`] where it must not be.
     pub async fn create(
         wallet: &impl BaseWallet,
         data: &[u8],
@@ -63,19 +109,41 @@ impl EncryptionEnvelope {
         their_service_id: &Uri,
     ) -> VcxResult<EncryptionEnvelope> {
         let sender_vk = resolve_base58_key_agreement(our_did_doc)?;
-        let recipient_key = resolve_base58_key_agreement(their_did_doc)?;
+        let recipient_keys = get_recipient_keys(their_did_doc, their_service_id)?;
         let routing_keys = get_routing_keys(their_did_doc, their_service_id)?;
 
-        EncryptionEnvelope::create_from_keys(
+        EncryptionEnvelope::create_from_typed_keys(
             wallet,
             data,
-            Some(&sender_vk.to_string()),
-            recipient_key.to_string(),
-            routing_keys.iter().map(|k| k.to_string()).collect(),
+            Some(&sender_vk),
+            recipient_keys,
+            routing_keys,
         )
         .await
     }
 
+    /// Same as [`EncryptionEnvelope::create`], but the message is anoncrypted: the counterparty
+    /// can decrypt it but does not learn who sent it.
+    ///
+    /// Authcrypt, via [`EncryptionEnvelope::create`], is the default whenever the counterparty
+    /// can verify our key against a DID document it holds: the DID exchange request (which
+    /// carries our DID document) and every message after it. Anoncrypt is meant for messages
+    /// sent to a peer that cannot know our key yet, e.g. a connectionless reply to an
+    /// out-of-band invitation, or for flows where our identity must not be revealed before
+    /// mutual authentication.
+    pub async fn create_anoncrypt(
+        wallet: &impl BaseWallet,
+        data: &[u8],
+        their_did_doc: &DidDocument,
+        their_service_id: &Uri,
+    ) -> VcxResult<EncryptionEnvelope> {
+        let recipient_keys = get_recipient_keys(their_did_doc, their_service_id)?;
+        let routing_keys = get_routing_keys(their_did_doc, their_service_id)?;
+
+        EncryptionEnvelope::create_from_typed_keys(wallet, data, None, recipient_keys, routing_keys)
+            .await
+    }
+
     pub async fn create_from_keys(
         wallet: &impl BaseWallet,
         data: &[u8],
@@ -83,31 +151,64 @@ impl EncryptionEnvelope {
         recipient_key: String,
         routing_keys: Vec<String>,
     ) -> VcxResult<EncryptionEnvelope> {
-        let message = EncryptionEnvelope::encrypt_for_pairwise(
+        Self::create_from_multiple_keys(wallet, data, sender_vk, vec![recipient_key], routing_keys)
+            .await
+    }
+
+    /// Create encrypted message readable by any of `recipient_keys`. If routing keys are
+    /// provided, the message is wrapped into forward messages addressed to the first recipient
+    /// key.
+    pub async fn create_from_multiple_keys(
+        wallet: &impl BaseWallet,
+        data: &[u8],
+        sender_vk: Option<&str>,
+        recipient_keys: Vec<String>,
+        routing_keys: Vec<String>,
+    ) -> VcxResult<EncryptionEnvelope> {
+        let recipient_keys = recipient_keys
+            .iter()
+            .map(|key| Key::from_base58(key, KeyType::Ed25519))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::create_from_typed_keys(wallet, data, sender_vk, recipient_keys, routing_keys).await
+    }
+
+    async fn create_from_typed_keys(
+        wallet: &impl BaseWallet,
+        data: &[u8],
+        sender_vk: Option<&str>,
+        recipient_keys: Vec<Key>,
+        routing_keys: Vec<String>,
+    ) -> VcxResult<EncryptionEnvelope> {
+        let forward_to_key = recipient_keys.first().map(Key::base58).ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidInput,
+                "Unable to encrypt message, no recipient keys provided",
+            )
+        })?;
+        let message =
+            EncryptionEnvelope::encrypt_for_pairwise(wallet, data, sender_vk, recipient_keys)
+                .await?;
+        EncryptionEnvelope::wrap_into_forward_messages(
             wallet,
-            data,
-            sender_vk,
-            recipient_key.clone(),
+            message,
+            forward_to_key,
+            routing_keys,
         )
-        .await?;
-        EncryptionEnvelope::wrap_into_forward_messages(wallet, message, recipient_key, routing_keys)
-            .await
-            .map(EncryptionEnvelope)
+        .await
+        .map(EncryptionEnvelope)
     }
 
     async fn encrypt_for_pairwise(
         wallet: &impl BaseWallet,
         data: &[u8],
         sender_vk: Option<&str>,
-        recipient_key: String,
+        recipient_keys: Vec<Key>,
     ) -> VcxResult<Vec<u8>> {
         debug!(
-            "Encrypting for pairwise; sender_vk: {:?}, recipient_key: {}",
-            sender_vk, recipient_key
+            "Encrypting for pairwise; sender_vk: {:?}, recipient_keys: {:?}",
+            sender_vk, recipient_keys
         );
 
-        let recipient_keys = vec![Key::from_base58(&recipient_key, KeyType::Ed25519)?];
-
         wallet
             .pack_message(
                 sender_vk
@@ -170,6 +271,38 @@ impl EncryptionEnvelope {
             .map_err(|err| err.into())
     }
 
+    /// Determines how `encrypted_data` was encrypted by inspecting the `alg` of its protected
+    /// header, without decrypting it. Useful to apply policy (e.g. rejecting anoncrypted
+    /// messages where the sender must be authenticated) before unpacking.
+    pub fn encryption_mode(encrypted_data: &[u8]) -> VcxResult<EncryptionMode> {
+        let jwe: Value = serde_json::from_slice(encrypted_data)?;
+        let protected = jwe
+            .get("protected")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidJson,
+                    "Encrypted message does not contain a protected header",
+                )
+            })?;
+        let protected = URL_SAFE_LENIENT.decode(protected).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Unable to decode protected header: {}", err),
+            )
+        })?;
+        let protected: Value = serde_json::from_slice(&protected)?;
+
+        match protected.get("alg").and_then(Value::as_str) {
+            Some("Authcrypt") => Ok(EncryptionMode::Authcrypt),
+            Some("Anoncrypt") => Ok(EncryptionMode::Anoncrypt),
+            alg => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Unsupported protected header alg: {:?}", alg),
+            )),
+        }
+    }
+
     async fn _unpack_a2a_message(
         wallet: &impl BaseWallet,
         encrypted_data: Vec<u8>,
@@ -272,10 +405,16 @@ impl EncryptionEnvelope {
 #[cfg(test)]
 pub mod unit_tests {
     use aries_vcx_wallet::wallet::base_wallet::did_wallet::DidWallet;
+    use did_peer::resolver::PeerDidResolver;
+    use did_resolver::traits::resolvable::DidResolvable;
     use serde_json::Value;
     use test_utils::devsetup::build_setup_profile;
+    use url::Url;
 
     use super::*;
+    use crate::protocols::did_exchange::state_machine::helpers::{
+        create_peer_did_4, create_peer_did_4_from_seed,
+    };
 
     #[tokio::test]
     async fn test_pack_unpack_anon() {
@@ -299,7 +438,7 @@ pub mod unit_tests {
         .unwrap();
 
         let (data_unpacked, sender_verkey) =
-            EncryptionEnvelope::anon_unpack(&setup.wallet, envelope.0)
+            EncryptionEnvelope::anon_unpack(&setup.wallet, envelope.into_bytes())
                 .await
                 .unwrap();
 
@@ -307,6 +446,27 @@ pub mod unit_tests {
         assert!(sender_verkey.is_none());
     }
 
+    #[test]
+    fn test_envelope_into_parts() {
+        let envelope = EncryptionEnvelope(b"foobar".to_vec());
+
+        let (bytes, content_type) = envelope.into_parts();
+
+        assert_eq!(bytes, b"foobar".to_vec());
+        assert_eq!(content_type, "application/ssi-agent-wire");
+    }
+
+    #[test]
+    fn test_envelope_byte_accessors() {
+        let envelope = EncryptionEnvelope(b"foobar".to_vec());
+        assert_eq!(envelope.as_bytes(), b"foobar");
+        assert_eq!(envelope.len(), 6);
+        assert!(!envelope.is_empty());
+        assert_eq!(envelope.into_bytes(), b"foobar".to_vec());
+
+        assert!(EncryptionEnvelope(vec![]).is_empty());
+    }
+
     #[tokio::test]
     async fn test_pack_unpack_auth() {
         let setup = build_setup_profile().await;
@@ -335,7 +495,7 @@ pub mod unit_tests {
 
         let data_unpacked = EncryptionEnvelope::auth_unpack(
             &setup.wallet,
-            envelope.0,
+            envelope.into_bytes(),
             &sender_data.verkey().base58(),
         )
         .await
@@ -344,6 +504,257 @@ pub mod unit_tests {
         assert_eq!(data_original, data_unpacked);
     }
 
+    #[tokio::test]
+    async fn test_encryption_mode() {
+        let setup = build_setup_profile().await;
+        let sender_data = setup
+            .wallet
+            .create_and_store_my_did(None, None)
+            .await
+            .unwrap();
+        let recipient_data = setup
+            .wallet
+            .create_and_store_my_did(None, None)
+            .await
+            .unwrap();
+
+        let anon_envelope = EncryptionEnvelope::create_from_keys(
+            &setup.wallet,
+            "foobar".as_bytes(),
+            None,
+            recipient_data.verkey().base58(),
+            [].to_vec(),
+        )
+        .await
+        .unwrap();
+        let auth_envelope = EncryptionEnvelope::create_from_keys(
+            &setup.wallet,
+            "foobar".as_bytes(),
+            Some(&sender_data.verkey().base58()),
+            recipient_data.verkey().base58(),
+            [].to_vec(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            EncryptionEnvelope::encryption_mode(anon_envelope.as_bytes()).unwrap(),
+            EncryptionMode::Anoncrypt
+        );
+        assert_eq!(
+            EncryptionEnvelope::encryption_mode(auth_envelope.as_bytes()).unwrap(),
+            EncryptionMode::Authcrypt
+        );
+        assert_eq!(
+            EncryptionEnvelope::encryption_mode(b"{}")
+                .unwrap_err()
+                .kind(),
+            AriesVcxErrorKind::InvalidJson
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pack_unpack_multiple_recipients() {
+        let setup = build_setup_profile().await;
+        let other_setup = build_setup_profile().await;
+        let sender_data = setup
+            .wallet
+            .create_and_store_my_did(None, None)
+            .await
+            .unwrap();
+        let recipient_data = setup
+            .wallet
+            .create_and_store_my_did(None, None)
+            .await
+            .unwrap();
+        let other_recipient_data = other_setup
+            .wallet
+            .create_and_store_my_did(None, None)
+            .await
+            .unwrap();
+
+        let data_original = "foobar";
+
+        let envelope = EncryptionEnvelope::create_from_multiple_keys(
+            &setup.wallet,
+            data_original.as_bytes(),
+            Some(&sender_data.verkey().base58()),
+            vec![
+                other_recipient_data.verkey().base58(),
+                recipient_data.verkey().base58(),
+            ],
+            [].to_vec(),
+        )
+        .await
+        .unwrap();
+
+        let data_unpacked = EncryptionEnvelope::auth_unpack(
+            &setup.wallet,
+            envelope.as_bytes().to_vec(),
+            &sender_data.verkey().base58(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(data_original, data_unpacked);
+
+        let other_data_unpacked = EncryptionEnvelope::auth_unpack(
+            &other_setup.wallet,
+            envelope.into_bytes(),
+            &sender_data.verkey().base58(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(data_original, other_data_unpacked);
+    }
+
+    #[tokio::test]
+    async fn test_pack_unpack_peer_did_4() {
+        let setup = build_setup_profile().await;
+        let service_endpoint: Url = "http://example.org".parse().unwrap();
+        let (our_peer_did, our_key) = create_peer_did_4_from_seed(
+            &setup.wallet,
+            "00000000000000000000000000Peer4A",
+            service_endpoint.clone(),
+            vec![],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            our_peer_did.short_form().to_string(),
+            "did:peer:4z84UiPbmJdcUf2r7Ga6Es3UPcYt9QSVuDrdHaNmVmPm83ebWHGH6N5SLusG3Q634DUgtmFkRiYAWxvcVLytFB58bacJ"
+        );
+        let (their_peer_did, _) = create_peer_did_4(&setup.wallet, service_endpoint, vec![])
+            .await
+            .unwrap();
+        let our_did_doc = our_peer_did.resolve_did_doc().unwrap();
+        let their_did_doc = their_peer_did.resolve_did_doc().unwrap();
+        let their_service_id = their_did_doc.service()[0].id().clone();
+
+        let data_original = "foobar";
+
+        let envelope = EncryptionEnvelope::create(
+            &setup.wallet,
+            data_original.as_bytes(),
+            &our_did_doc,
+            &their_did_doc,
+            &their_service_id,
+        )
+        .await
+        .unwrap();
+
+        let data_unpacked = EncryptionEnvelope::auth_unpack(
+            &setup.wallet,
+            envelope.into_bytes(),
+            &our_key.base58(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(data_original, data_unpacked);
+    }
+
+    #[tokio::test]
+    async fn test_pack_unpack_anoncrypt_peer_did_4() {
+        let setup = build_setup_profile().await;
+        let (their_peer_did, _) =
+            create_peer_did_4(&setup.wallet, "http://example.org".parse().unwrap(), vec![])
+                .await
+                .unwrap();
+        let their_did_doc = their_peer_did.resolve_did_doc().unwrap();
+        let their_service_id = their_did_doc.service()[0].id().clone();
+
+        let data_original = "foobar";
+
+        let envelope = EncryptionEnvelope::create_anoncrypt(
+            &setup.wallet,
+            data_original.as_bytes(),
+            &their_did_doc,
+            &their_service_id,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            EncryptionEnvelope::encryption_mode(envelope.as_bytes()).unwrap(),
+            EncryptionMode::Anoncrypt
+        );
+
+        let (data_unpacked, sender_verkey) =
+            EncryptionEnvelope::anon_unpack(&setup.wallet, envelope.into_bytes())
+                .await
+                .unwrap();
+
+        assert_eq!(data_original, data_unpacked);
+        assert!(sender_verkey.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pack_unpack_peer_did_2() {
+        let setup = build_setup_profile().await;
+        let (our_peer_did, our_key) =
+            create_peer_did_4(&setup.wallet, "http://example.org".parse().unwrap(), vec![])
+                .await
+                .unwrap();
+        let our_did_doc = our_peer_did.resolve_did_doc().unwrap();
+        let recipient_data = setup
+            .wallet
+            .create_and_store_my_did(None, None)
+            .await
+            .unwrap();
+        // X25519 key agreement key, Ed25519 verification key held by the wallet, DIDComm service
+        let their_did = format!(
+            "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.V{}.\
+             SeyJpZCI6IiNzZXJ2aWNlLTAiLCJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCJ9",
+            recipient_data.verkey().fingerprint()
+        )
+        .parse()
+        .unwrap();
+        let their_did_doc = PeerDidResolver::new()
+            .resolve(&their_did, &Default::default())
+            .await
+            .unwrap()
+            .did_document;
+        let their_service_id = their_did_doc.service()[0].id().clone();
+
+        let data_original = "foobar";
+
+        let envelope = EncryptionEnvelope::create(
+            &setup.wallet,
+            data_original.as_bytes(),
+            &our_did_doc,
+            &their_did_doc,
+            &their_service_id,
+        )
+        .await
+        .unwrap();
+
+        let data_unpacked = EncryptionEnvelope::auth_unpack(
+            &setup.wallet,
+            envelope.into_bytes(),
+            &our_key.base58(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(data_original, data_unpacked);
+    }
+
+    #[tokio::test]
+    async fn test_pack_no_recipients_fails() {
+        let setup = build_setup_profile().await;
+
+        let err = EncryptionEnvelope::create_from_multiple_keys(
+            &setup.wallet,
+            "foobar".as_bytes(),
+            None,
+            [].to_vec(),
+            [].to_vec(),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidInput);
+    }
+
     #[tokio::test]
     async fn test_pack_unpack_with_routing() {
         let setup = build_setup_profile().await;
@@ -375,7 +786,7 @@ pub mod unit_tests {
         .await
         .unwrap();
 
-        let (fwd_msg, _) = EncryptionEnvelope::anon_unpack(&setup.wallet, envelope.0)
+        let (fwd_msg, _) = EncryptionEnvelope::anon_unpack(&setup.wallet, envelope.into_bytes())
             .await
             .unwrap();
         let fwd_payload = serde_json::from_str::<Value>(&fwd_msg)
@@ -423,7 +834,7 @@ pub mod unit_tests {
 
         let err = EncryptionEnvelope::auth_unpack(
             &setup.wallet,
-            envelope.0,
+            envelope.into_bytes(),
             &alice_data.verkey().base58(),
         )
         .await;
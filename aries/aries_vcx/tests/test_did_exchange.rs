@@ -125,6 +125,8 @@ async fn did_exchange_test() -> Result<(), Box<dyn Error>> {
         Some(invitation.id),
         &did_inviter,
         &requesters_peer_did,
+        None,
+        None,
     )
     .await
     .unwrap();
@@ -8,7 +8,7 @@ use public_key::Key;
 
 use self::{
     askar_utils::local_key_to_bs58_public_key, askar_wallet_config::AskarWalletConfig,
-    rng_method::RngMethod,
+    key_method::KeyMethod, rng_method::RngMethod,
 };
 use super::{
     base_wallet::{
@@ -98,6 +98,21 @@ impl AskarWallet {
         })
     }
 
+    /// Re-encrypts the wallet with a new pass key, derived using `key_method`.
+    ///
+    /// Requires exclusive access to the wallet, so it should only be called while no other
+    /// operations are in progress.
+    pub async fn rekey(
+        &mut self,
+        key_method: KeyMethod,
+        new_pass_key: &str,
+    ) -> VcxWalletResult<()> {
+        Ok(self
+            .backend
+            .rekey(key_method.into(), new_pass_key.into())
+            .await?)
+    }
+
     async fn fetch(
         &self,
         session: &mut Session,
@@ -238,15 +253,15 @@ impl AskarWallet {
 
 #[cfg(test)]
 pub mod tests {
+    use uuid::Uuid;
+
     use super::AskarWallet;
     use crate::wallet::{
         askar::{askar_wallet_config::AskarWalletConfig, key_method::KeyMethod},
-        base_wallet::ManageWallet,
+        base_wallet::{did_wallet::DidWallet, ManageWallet},
     };
 
     pub async fn dev_setup_askar_wallet() -> AskarWallet {
-        use uuid::Uuid;
-
         let config = AskarWalletConfig::new(
             "sqlite://:memory:",
             KeyMethod::Unprotected,
@@ -256,4 +271,45 @@ pub mod tests {
 
         config.create_wallet().await.unwrap()
     }
+
+    fn new_raw_key() -> String {
+        bs58::encode(rand::random::<[u8; 32]>()).into_string()
+    }
+
+    #[tokio::test]
+    async fn test_askar_should_rekey_wallet() {
+        let db_path = std::env::temp_dir().join(format!("askar_rekey_{}.db", Uuid::new_v4()));
+        let db_url = format!("sqlite://{}", db_path.display());
+        let profile = Uuid::new_v4().to_string();
+        let old_pass_key = new_raw_key();
+        let new_pass_key = new_raw_key();
+
+        let mut wallet =
+            AskarWalletConfig::new(&db_url, KeyMethod::RawKey, &old_pass_key, &profile)
+                .create_wallet()
+                .await
+                .unwrap();
+        let did_data = wallet.create_and_store_my_did(None, None).await.unwrap();
+        wallet
+            .rekey(KeyMethod::RawKey, &new_pass_key)
+            .await
+            .unwrap();
+        drop(wallet);
+
+        assert!(
+            AskarWalletConfig::new(&db_url, KeyMethod::RawKey, &old_pass_key, &profile)
+                .open_wallet()
+                .await
+                .is_err()
+        );
+        let wallet = AskarWalletConfig::new(&db_url, KeyMethod::RawKey, &new_pass_key, &profile)
+            .open_wallet()
+            .await
+            .unwrap();
+        let verkey = wallet.key_for_did(did_data.did()).await.unwrap();
+        assert_eq!(verkey.base58(), did_data.verkey().base58());
+
+        drop(wallet);
+        let _ = std::fs::remove_file(db_path);
+    }
 }
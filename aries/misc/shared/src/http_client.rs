@@ -8,6 +8,11 @@ use reqwest::{
 
 use crate::errors::http_error::{HttpError, HttpResult};
 
+/// Media type under which DIDComm V1 encrypted envelopes are posted. This is the legacy type of
+/// Aries RFC 0044 (`application/didcomm-envelope-enc` being the current one), kept because it is
+/// what agents in the wild accept.
+pub const DIDCOMM_V1_ENVELOPE_CONTENT_TYPE: &str = "application/ssi-agent-wire";
+
 lazy_static! {
     static ref HTTP_CLIENT: Client = {
         match reqwest::ClientBuilder::new()
@@ -32,7 +37,7 @@ async fn send_post_request(url: &Url, body_content: Vec<u8>) -> HttpResult<Respo
     HTTP_CLIENT
         .post(url.clone())
         .body(body_content)
-        .header(CONTENT_TYPE, "application/ssi-agent-wire")
+        .header(CONTENT_TYPE, DIDCOMM_V1_ENVELOPE_CONTENT_TYPE)
         .header(USER_AGENT, "reqwest")
         .send()
         .await
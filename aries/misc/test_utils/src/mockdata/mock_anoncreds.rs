@@ -56,9 +56,7 @@ impl BaseAnonCreds for MockAnoncreds {
         _rev_reg_defs_json: Option<RevocationRegistryDefinitionsMap>,
         _rev_regs_json: Option<RevocationRegistriesMap>,
     ) -> VcxAnoncredsResult<bool> {
-        Err(VcxAnoncredsError::UnimplementedFeature(
-            "unimplemented mock method: verifier_verify_proof".into(),
-        ))
+        Ok(true)
     }
 
     async fn issuer_create_and_store_revoc_reg(
@@ -1,7 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use display_as_json::Display;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
 use service_accept_type::ServiceAcceptType;
 use service_key_kind::ServiceKeyKind;
@@ -25,9 +25,22 @@ pub struct Service {
     service_endpoint: Url,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(serialize_with = "serialize_sorted")]
     extra: HashMap<String, Value>,
 }
 
+/// Serializes the extra fields ordered by key, so that the same service always serializes to the
+/// same JSON. Peer DIDs hash the serialized document, which must therefore be stable.
+fn serialize_sorted<S>(extra: &HashMap<String, Value>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    extra
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
+}
+
 impl Service {
     pub fn new(
         id: Uri,
@@ -193,6 +206,27 @@ mod tests {
         assert_eq!(service.service_type(), &OneOrList::One(service_type));
     }
 
+    #[test]
+    fn test_serialize_service_extra_fields_sorted() {
+        let mut service = Service::new(
+            Uri::new("#0").unwrap(),
+            "https://example.com/endpoint".try_into().unwrap(),
+            OneOrList::One(ServiceType::DIDCommV1),
+            HashMap::default(),
+        );
+        service.add_extra_field_routing_keys(vec![]).unwrap();
+        service.add_extra_field_priority(0).unwrap();
+        service
+            .add_extra_field_accept(vec![ServiceAcceptType::DIDCommV1])
+            .unwrap();
+        service.add_extra_field_recipient_keys(vec![]).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&service).unwrap(),
+            r##"{"id":"#0","type":"did-communication","serviceEndpoint":"https://example.com/endpoint","accept":["didcomm/aip2;env=rfc19"],"priority":0,"recipientKeys":[],"routingKeys":[]}"##
+        );
+    }
+
     #[test]
     fn test_serde_service_aip1() {
         let service_aip1 = json!({
@@ -0,0 +1,9 @@
+use public_key::KeyType;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum DidKeyResolverError {
+    #[error("Unsupported key type: {0:?}")]
+    UnsupportedKeyType(KeyType),
+}
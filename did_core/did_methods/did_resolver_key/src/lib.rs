@@ -0,0 +1,112 @@
+pub mod error;
+
+use async_trait::async_trait;
+use did_key::DidKey;
+use did_resolver::{
+    did_doc::schema::{
+        did_doc::DidDocument,
+        verification_method::{PublicKeyField, VerificationMethod, VerificationMethodType},
+    },
+    did_parser_nom::{Did, DidUrl},
+    error::GenericError,
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
+        DidResolvable,
+    },
+};
+use error::DidKeyResolverError;
+use public_key::KeyType;
+
+/// Resolves `did:key` DIDs into a document holding the single key encoded in the DID, which is
+/// both the only verification method and the key agreement key.
+#[derive(Default)]
+pub struct DidKeyResolver;
+
+impl DidKeyResolver {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DidResolvable for DidKeyResolver {
+    type DidResolutionOptions = ();
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        _options: &Self::DidResolutionOptions,
+    ) -> Result<DidResolutionOutput, GenericError> {
+        let did_key = DidKey::parse(did.to_string())?;
+        let key = did_key.key();
+        let verification_method_type = match *key.key_type() {
+            KeyType::Ed25519 => VerificationMethodType::Ed25519VerificationKey2018,
+            KeyType::X25519 => VerificationMethodType::X25519KeyAgreementKey2019,
+            key_type => return Err(Box::new(DidKeyResolverError::UnsupportedKeyType(key_type))),
+        };
+        let verification_method = VerificationMethod::builder()
+            .id(DidUrl::parse(format!("{}#{}", did_key, key.fingerprint()))?)
+            .controller(did_key.did().clone())
+            .verification_method_type(verification_method_type)
+            .public_key(PublicKeyField::Base58 {
+                public_key_base58: key.base58(),
+            })
+            .build();
+
+        let mut did_doc = DidDocument::new(did_key.did().clone());
+        did_doc.add_verification_method(verification_method.clone());
+        did_doc.add_key_agreement_object(verification_method);
+
+        let resolution_metadata = DidResolutionMetadata::builder()
+            .content_type("application/did+json".to_string())
+            .build();
+        Ok(DidResolutionOutput::builder(did_doc)
+            .did_resolution_metadata(resolution_metadata)
+            .build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolve_ed25519_did_key() {
+        let did: Did = "did:key:z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th"
+            .parse()
+            .unwrap();
+
+        let did_doc = DidKeyResolver::new()
+            .resolve(&did, &())
+            .await
+            .unwrap()
+            .did_document;
+
+        assert_eq!(did_doc.id(), &did);
+        assert_eq!(did_doc.verification_method().len(), 1);
+        let verification_method = &did_doc.verification_method()[0];
+        assert_eq!(
+            verification_method.id().to_string(),
+            "did:key:z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th#\
+             z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th"
+        );
+        assert_eq!(
+            verification_method.verification_method_type(),
+            &VerificationMethodType::Ed25519VerificationKey2018
+        );
+        assert_eq!(did_doc.key_agreement().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_rejects_unsupported_key_type() {
+        let did: Did = "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169"
+            .parse()
+            .unwrap();
+
+        let err = DidKeyResolver::new().resolve(&did, &()).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DidKeyResolverError>(),
+            Some(DidKeyResolverError::UnsupportedKeyType(KeyType::P256))
+        ));
+    }
+}
@@ -2,14 +2,18 @@ use std::error::Error;
 
 #[derive(Debug)]
 pub enum DidResolverRegistryError {
-    UnsupportedMethod,
+    UnsupportedMethod(String),
     UnqualifiedDid,
 }
 
 impl std::fmt::Display for DidResolverRegistryError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            DidResolverRegistryError::UnsupportedMethod => write!(f, "Unsupported DID method"),
+            DidResolverRegistryError::UnsupportedMethod(method) => write!(
+                f,
+                "Unsupported DID method: \"{}\", no resolver is registered for it",
+                method
+            ),
             DidResolverRegistryError::UnqualifiedDid => {
                 write!(f, "Attempted to resolve unqualified DID")
             }
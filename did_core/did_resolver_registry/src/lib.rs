@@ -95,10 +95,12 @@ impl ResolverRegistry {
     ) -> Result<DidResolutionOutput, GenericError> {
         let method = did
             .method()
-            .ok_or(DidResolverRegistryError::UnsupportedMethod)?;
+            .ok_or(DidResolverRegistryError::UnqualifiedDid)?;
         match self.resolvers.get(method) {
             Some(resolver) => resolver.resolve(did, options.clone()).await,
-            None => Err(Box::new(DidResolverRegistryError::UnsupportedMethod)),
+            None => Err(Box::new(DidResolverRegistryError::UnsupportedMethod(
+                method.to_string(),
+            ))),
         }
     }
 }
@@ -228,7 +230,26 @@ mod tests {
         assert!(
             matches!(
                 error.downcast_ref::<DidResolverRegistryError>(),
-                Some(DidResolverRegistryError::UnsupportedMethod)
+                Some(DidResolverRegistryError::UnsupportedMethod(method)) if method == "unknown"
+            ),
+            "Error is not of type DidResolverRegistryError"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_unqualified_did() {
+        let did = Did::parse("2ZHFFhzA2XtTD6hJqzL7ux".to_string()).unwrap();
+        assert!(did.method().is_none());
+
+        let registry = ResolverRegistry::new();
+        let result = registry.resolve(&did, &HashMap::new()).await;
+
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(
+            matches!(
+                error.downcast_ref::<DidResolverRegistryError>(),
+                Some(DidResolverRegistryError::UnqualifiedDid)
             ),
             "Error is not of type DidResolverRegistryError"
         );
@@ -263,7 +284,7 @@ mod tests {
         assert!(
             matches!(
                 error_before.downcast_ref::<DidResolverRegistryError>(),
-                Some(DidResolverRegistryError::UnsupportedMethod)
+                Some(DidResolverRegistryError::UnsupportedMethod(_))
             ),
             "Error is not of type DidResolverRegistryError"
         );